@@ -0,0 +1,112 @@
+//! Decoding uploaded audio (wav/mp3) down to mono `f32` PCM for fingerprinting.
+
+use std::io::Cursor;
+
+/// Sample rate every decoded clip is resampled to before fingerprinting.
+///
+/// [`crate::fingerprint`] hashes purely in FFT-bin space, and bin-to-frequency mapping
+/// depends on `sample_rate / WINDOW_SIZE`. Without a canonical rate, the same recording
+/// ingested from a 48 kHz file and queried from a 44.1 kHz clip would land identical
+/// real-world frequencies in different bins and never match. The WebSocket capture path
+/// ([`crate::ws`]) assumes its raw PCM stream already arrives at this rate.
+pub const TARGET_SAMPLE_RATE: u32 = 44_100;
+
+/// Container formats accepted by the ingestion and recognition endpoints.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AudioFormat {
+    Wav,
+    Mp3,
+}
+
+impl AudioFormat {
+    /// Guess the format from a client-supplied filename's extension.
+    pub fn from_filename(filename: &str) -> Option<Self> {
+        let ext = filename.rsplit('.').next()?.to_ascii_lowercase();
+        match ext.as_str() {
+            "wav" => Some(AudioFormat::Wav),
+            "mp3" => Some(AudioFormat::Mp3),
+            _ => None,
+        }
+    }
+}
+
+/// Decode `bytes` to mono `f32` PCM in `[-1.0, 1.0]`, resampled to [`TARGET_SAMPLE_RATE`]
+/// so every caller hashes comparably regardless of the source file's original rate.
+pub fn decode_to_mono_pcm(bytes: &[u8], format: AudioFormat) -> std::io::Result<Vec<f32>> {
+    let (samples, sample_rate) = match format {
+        AudioFormat::Wav => decode_wav(bytes)?,
+        AudioFormat::Mp3 => decode_mp3(bytes)?,
+    };
+    Ok(resample_linear(&samples, sample_rate, TARGET_SAMPLE_RATE))
+}
+
+/// Linearly resample `samples` from `from_rate` to `to_rate`.
+fn resample_linear(samples: &[f32], from_rate: u32, to_rate: u32) -> Vec<f32> {
+    if from_rate == to_rate || samples.is_empty() {
+        return samples.to_vec();
+    }
+
+    let ratio = from_rate as f64 / to_rate as f64;
+    let out_len = ((samples.len() as f64) / ratio).round() as usize;
+    (0..out_len)
+        .map(|i| {
+            let src_pos = i as f64 * ratio;
+            let idx = src_pos.floor() as usize;
+            let frac = (src_pos - idx as f64) as f32;
+            let a = samples[idx.min(samples.len() - 1)];
+            let b = samples[(idx + 1).min(samples.len() - 1)];
+            a + (b - a) * frac
+        })
+        .collect()
+}
+
+fn decode_wav(bytes: &[u8]) -> std::io::Result<(Vec<f32>, u32)> {
+    let reader = hound::WavReader::new(Cursor::new(bytes))
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+    let spec = reader.spec();
+    let channels = spec.channels as usize;
+
+    let samples: Vec<f32> = match spec.sample_format {
+        hound::SampleFormat::Int => {
+            let max = (1i64 << (spec.bits_per_sample - 1)) as f32;
+            reader
+                .into_samples::<i32>()
+                .map(|s| s.unwrap_or(0) as f32 / max)
+                .collect()
+        }
+        hound::SampleFormat::Float => reader.into_samples::<f32>().map(|s| s.unwrap_or(0.0)).collect(),
+    };
+
+    Ok((to_mono(&samples, channels), spec.sample_rate))
+}
+
+fn decode_mp3(bytes: &[u8]) -> std::io::Result<(Vec<f32>, u32)> {
+    let mut decoder = minimp3::Decoder::new(Cursor::new(bytes));
+    let mut samples = Vec::new();
+    let mut sample_rate = 0;
+    let mut channels = 1;
+
+    loop {
+        match decoder.next_frame() {
+            Ok(frame) => {
+                sample_rate = frame.sample_rate as u32;
+                channels = frame.channels;
+                samples.extend(frame.data.iter().map(|&s| s as f32 / i16::MAX as f32));
+            }
+            Err(minimp3::Error::Eof) => break,
+            Err(e) => return Err(std::io::Error::new(std::io::ErrorKind::InvalidData, e)),
+        }
+    }
+
+    Ok((to_mono(&samples, channels), sample_rate))
+}
+
+fn to_mono(samples: &[f32], channels: usize) -> Vec<f32> {
+    if channels <= 1 {
+        return samples.to_vec();
+    }
+    samples
+        .chunks(channels)
+        .map(|frame| frame.iter().sum::<f32>() / channels as f32)
+        .collect()
+}
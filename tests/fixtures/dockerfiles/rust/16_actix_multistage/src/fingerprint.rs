@@ -0,0 +1,173 @@
+//! Shazam-style acoustic fingerprinting: spectrogram -> constellation map -> landmark hashes.
+//!
+//! This module is shared by the ingestion (`/tracks`) and recognition (`/recognize`)
+//! handlers so the exact same hashing recipe is used on both sides of the lookup.
+
+use rustfft::{num_complex::Complex, FftPlanner};
+
+/// STFT window size, in samples.
+pub const WINDOW_SIZE: usize = 4096;
+/// Hop between successive windows (50% overlap).
+pub const HOP_SIZE: usize = WINDOW_SIZE / 2;
+/// Neighborhood (in bins) a peak must dominate in both time and frequency.
+const NEIGHBORHOOD: usize = 10;
+/// How far forward in time (in STFT frames) an anchor's target zone extends.
+const TARGET_ZONE_DT: usize = 64;
+/// How many points a single anchor pairs with in its target zone.
+const FAN_OUT: usize = 5;
+
+/// A single point in the constellation map: a prominent (time, frequency) bin.
+#[derive(Debug, Clone, Copy)]
+pub struct Peak {
+    pub time: usize,
+    pub freq: usize,
+    pub magnitude: f32,
+}
+
+/// One landmark hash plus the absolute time (in STFT frames) of its anchor point.
+#[derive(Debug, Clone, Copy)]
+pub struct Landmark {
+    pub hash: u32,
+    pub anchor_time: u32,
+}
+
+/// Compute a magnitude spectrogram from mono PCM samples using a Hann-windowed STFT.
+///
+/// Returns one `Vec<f32>` of bin magnitudes per frame.
+pub fn spectrogram(samples: &[f32]) -> Vec<Vec<f32>> {
+    if samples.len() < WINDOW_SIZE {
+        return Vec::new();
+    }
+
+    let window: Vec<f32> = (0..WINDOW_SIZE)
+        .map(|i| 0.5 - 0.5 * (2.0 * std::f32::consts::PI * i as f32 / (WINDOW_SIZE - 1) as f32).cos())
+        .collect();
+
+    let mut planner = FftPlanner::<f32>::new();
+    let fft = planner.plan_fft_forward(WINDOW_SIZE);
+
+    let mut frames = Vec::new();
+    let mut start = 0;
+    while start + WINDOW_SIZE <= samples.len() {
+        let mut buf: Vec<Complex<f32>> = samples[start..start + WINDOW_SIZE]
+            .iter()
+            .zip(&window)
+            .map(|(s, w)| Complex::new(s * w, 0.0))
+            .collect();
+        fft.process(&mut buf);
+
+        // Only the first half is unique for real-valued input.
+        let magnitudes = buf[..WINDOW_SIZE / 2].iter().map(|c| c.norm()).collect();
+        frames.push(magnitudes);
+
+        start += HOP_SIZE;
+    }
+    frames
+}
+
+/// Amplitude threshold factor applied to a peak's own local neighborhood mean.
+const THRESHOLD_FACTOR: f32 = 2.0;
+
+/// Find local maxima in the spectrogram that dominate their time/frequency neighborhood
+/// and clear an amplitude threshold relative to that same neighborhood, forming the
+/// constellation map.
+///
+/// The threshold is local (the mean magnitude of the bins around the candidate peak),
+/// not a function of the whole clip's mean. A whole-clip threshold would make the same
+/// segment of audio hash differently depending on how long the surrounding recording
+/// is, which thins out the histogram votes recognition depends on when a full track is
+/// matched against a short query clip of the same passage.
+pub fn find_peaks(spectrogram: &[Vec<f32>]) -> Vec<Peak> {
+    if spectrogram.is_empty() {
+        return Vec::new();
+    }
+    let num_bins = spectrogram[0].len();
+
+    let mut peaks = Vec::new();
+    for t in 0..spectrogram.len() {
+        for f in 0..num_bins {
+            let magnitude = spectrogram[t][f];
+
+            let t_lo = t.saturating_sub(NEIGHBORHOOD);
+            let t_hi = (t + NEIGHBORHOOD).min(spectrogram.len() - 1);
+            let f_lo = f.saturating_sub(NEIGHBORHOOD);
+            let f_hi = (f + NEIGHBORHOOD).min(num_bins - 1);
+
+            let mut neighborhood_total = 0.0f64;
+            let mut neighborhood_count = 0u32;
+            let mut is_local_max = true;
+            for nt in t_lo..=t_hi {
+                for nf in f_lo..=f_hi {
+                    if (nt, nf) == (t, f) {
+                        continue;
+                    }
+                    let neighbor = spectrogram[nt][nf];
+                    neighborhood_total += neighbor as f64;
+                    neighborhood_count += 1;
+                    if neighbor > magnitude {
+                        is_local_max = false;
+                    }
+                }
+            }
+            if !is_local_max {
+                continue;
+            }
+
+            let local_mean = if neighborhood_count > 0 {
+                (neighborhood_total / neighborhood_count as f64) as f32
+            } else {
+                0.0
+            };
+            if magnitude < local_mean * THRESHOLD_FACTOR {
+                continue;
+            }
+
+            peaks.push(Peak { time: t, freq: f, magnitude });
+        }
+    }
+    peaks
+}
+
+/// Pair each anchor peak with several peaks in a forward target zone and pack
+/// `(f1, f2, dt)` into a 32-bit hash, Shazam-style.
+pub fn generate_hashes(peaks: &[Peak]) -> Vec<Landmark> {
+    let mut sorted = peaks.to_vec();
+    sorted.sort_by_key(|p| p.time);
+
+    let mut landmarks = Vec::new();
+    for (i, anchor) in sorted.iter().enumerate() {
+        let mut paired = 0;
+        for target in &sorted[i + 1..] {
+            let dt = target.time.saturating_sub(anchor.time);
+            if dt == 0 {
+                continue;
+            }
+            if dt > TARGET_ZONE_DT {
+                break;
+            }
+
+            landmarks.push(Landmark {
+                hash: pack_hash(anchor.freq as u32, target.freq as u32, dt as u32),
+                anchor_time: anchor.time as u32,
+            });
+
+            paired += 1;
+            if paired >= FAN_OUT {
+                break;
+            }
+        }
+    }
+    landmarks
+}
+
+/// Pack `(f1, f2, dt)` into a single 32-bit hash: 12 bits per frequency bin, 8 bits for dt.
+fn pack_hash(f1: u32, f2: u32, dt: u32) -> u32 {
+    ((f1 & 0xFFF) << 20) | ((f2 & 0xFFF) << 8) | (dt & 0xFF)
+}
+
+/// Run the full pipeline (spectrogram -> peaks -> hashes) over mono PCM samples.
+pub fn fingerprint(samples: &[f32]) -> Vec<Landmark> {
+    let spectrogram = spectrogram(samples);
+    let peaks = find_peaks(&spectrogram);
+    generate_hashes(&peaks)
+}
@@ -0,0 +1,78 @@
+//! Concurrent fingerprint database: landmark hash -> posting list, plus track metadata.
+//!
+//! Registered once as `web::Data` and shared by every worker thread, so ingestion and
+//! recognition actually see each other's data. Postings are sharded across several
+//! `RwLock<HashMap>`s keyed by hash so recognition's read-heavy lookups don't serialize
+//! behind occasional ingests, or each other.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::RwLock;
+
+use crate::fingerprint::Landmark;
+
+/// Metadata submitted alongside an ingested track.
+#[derive(Debug, Clone)]
+pub struct TrackMeta {
+    pub title: String,
+    pub artist: String,
+}
+
+const SHARD_COUNT: usize = 16;
+
+/// `hash -> (absolute_time_offset, track_id)` postings, sharded for concurrent access,
+/// plus the track metadata table.
+pub struct FingerprintIndex {
+    shards: Vec<RwLock<HashMap<u32, Vec<(u32, u32)>>>>,
+    tracks: RwLock<HashMap<u32, TrackMeta>>,
+    next_track_id: AtomicU32,
+}
+
+impl FingerprintIndex {
+    pub fn new() -> Self {
+        Self {
+            shards: (0..SHARD_COUNT).map(|_| RwLock::new(HashMap::new())).collect(),
+            tracks: RwLock::new(HashMap::new()),
+            next_track_id: AtomicU32::new(0),
+        }
+    }
+
+    fn shard_for(&self, hash: u32) -> &RwLock<HashMap<u32, Vec<(u32, u32)>>> {
+        &self.shards[hash as usize % self.shards.len()]
+    }
+
+    /// Store `landmarks` under a freshly generated track id and return it.
+    pub fn insert_track(&self, landmarks: &[Landmark], meta: TrackMeta) -> u32 {
+        let track_id = self.next_track_id.fetch_add(1, Ordering::Relaxed);
+        for landmark in landmarks {
+            self.shard_for(landmark.hash)
+                .write()
+                .unwrap()
+                .entry(landmark.hash)
+                .or_default()
+                .push((landmark.anchor_time, track_id));
+        }
+        self.tracks.write().unwrap().insert(track_id, meta);
+        track_id
+    }
+
+    /// All `(db_time, track_id)` postings for a given landmark hash.
+    pub fn lookup(&self, hash: u32) -> Vec<(u32, u32)> {
+        self.shard_for(hash)
+            .read()
+            .unwrap()
+            .get(&hash)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    pub fn track(&self, track_id: u32) -> Option<TrackMeta> {
+        self.tracks.read().unwrap().get(&track_id).cloned()
+    }
+}
+
+impl Default for FingerprintIndex {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -1,4 +1,20 @@
-use actix_web::{get, App, HttpResponse, HttpServer, Responder};
+mod audio;
+mod fingerprint;
+mod index;
+mod metrics;
+mod recognize;
+mod timing;
+mod ws;
+
+use actix_multipart::Multipart;
+use actix_web::{get, middleware::Logger, post, web, App, HttpRequest, HttpResponse, HttpServer, Responder};
+use futures_util::TryStreamExt;
+use serde::Deserialize;
+use std::time::Instant;
+
+use audio::AudioFormat;
+use index::{FingerprintIndex, TrackMeta};
+use metrics::Metrics;
 
 #[get("/")]
 async fn hello() -> impl Responder {
@@ -10,10 +26,162 @@ async fn health() -> impl Responder {
     HttpResponse::Ok().json(serde_json::json!({"status": "healthy"}))
 }
 
+/// Ingest a track: multipart fields `file` (wav/mp3), `title`, `artist`. Decodes the
+/// upload to mono PCM, fingerprints it, and stores the landmark hashes under a new
+/// track id.
+#[post("/tracks")]
+async fn ingest_track(
+    index: web::Data<FingerprintIndex>,
+    metrics: web::Data<Metrics>,
+    mut payload: Multipart,
+) -> actix_web::Result<impl Responder> {
+    let mut file_bytes: Option<Vec<u8>> = None;
+    let mut filename = String::new();
+    let mut title = String::new();
+    let mut artist = String::new();
+
+    while let Some(mut field) = payload.try_next().await? {
+        let name = field.name().to_string();
+        match name.as_str() {
+            "file" => {
+                filename = field
+                    .content_disposition()
+                    .and_then(|cd| cd.get_filename())
+                    .unwrap_or_default()
+                    .to_string();
+                let mut bytes = Vec::new();
+                while let Some(chunk) = field.try_next().await? {
+                    bytes.extend_from_slice(&chunk);
+                }
+                file_bytes = Some(bytes);
+            }
+            "title" => title = read_text_field(&mut field).await?,
+            "artist" => artist = read_text_field(&mut field).await?,
+            _ => {}
+        }
+    }
+
+    let bytes = file_bytes
+        .ok_or_else(|| actix_web::error::ErrorBadRequest("missing \"file\" field"))?;
+    let format = AudioFormat::from_filename(&filename)
+        .ok_or_else(|| actix_web::error::ErrorBadRequest("unsupported or missing file extension"))?;
+
+    let samples = audio::decode_to_mono_pcm(&bytes, format).map_err(actix_web::error::ErrorBadRequest)?;
+
+    let fingerprint_start = Instant::now();
+    let landmarks = fingerprint::fingerprint(&samples);
+    metrics.record_fingerprint(fingerprint_start.elapsed().as_millis() as u64);
+    let hash_count = landmarks.len();
+
+    let track_id = index.insert_track(&landmarks, TrackMeta { title, artist });
+
+    Ok(web::Json(serde_json::json!({
+        "track_id": track_id,
+        "hashes": hash_count,
+    })))
+}
+
+async fn read_text_field(field: &mut actix_multipart::Field) -> actix_web::Result<String> {
+    let mut bytes = Vec::new();
+    while let Some(chunk) = field.try_next().await? {
+        bytes.extend_from_slice(&chunk);
+    }
+    Ok(String::from_utf8_lossy(&bytes).into_owned())
+}
+
+#[derive(Deserialize)]
+struct RecognizeQuery {
+    format: String,
+}
+
+/// Recognize a short clip: the raw audio bytes as the request body, with `?format=wav|mp3`
+/// identifying the container. Fingerprints the clip and aligns it against the shared
+/// index via time-offset histogram voting.
+#[post("/recognize")]
+async fn recognize_track(
+    index: web::Data<FingerprintIndex>,
+    metrics: web::Data<Metrics>,
+    query: web::Query<RecognizeQuery>,
+    body: web::Bytes,
+) -> actix_web::Result<impl Responder> {
+    let format = match query.format.to_ascii_lowercase().as_str() {
+        "wav" => AudioFormat::Wav,
+        "mp3" => AudioFormat::Mp3,
+        _ => return Err(actix_web::error::ErrorBadRequest("format must be \"wav\" or \"mp3\"")),
+    };
+
+    let samples = audio::decode_to_mono_pcm(&body, format).map_err(actix_web::error::ErrorBadRequest)?;
+
+    let fingerprint_start = Instant::now();
+    let landmarks = fingerprint::fingerprint(&samples);
+    metrics.record_fingerprint(fingerprint_start.elapsed().as_millis() as u64);
+
+    let best = recognize::best_match(&landmarks, &index);
+    metrics.record_recognition(best.as_ref().map(|m| m.confidence));
+
+    match best {
+        Some(m) => Ok(HttpResponse::Ok().json(serde_json::json!({
+            "track_id": m.track_id,
+            "title": m.meta.title,
+            "artist": m.meta.artist,
+            "confidence": m.confidence,
+            "offset": m.offset,
+        }))),
+        None => Ok(HttpResponse::NotFound().json(serde_json::json!({
+            "error": "no match found",
+        }))),
+    }
+}
+
+/// Prometheus-style scrape endpoint: cumulative request latency/counts, recognition
+/// hits, misses and winning-bin votes, and fingerprint extraction time. Hit rate and
+/// average vote strength are derived from these counters by the scraper.
+#[get("/metrics")]
+async fn metrics_endpoint(metrics: web::Data<Metrics>) -> impl Responder {
+    HttpResponse::Ok()
+        .content_type("text/plain; version=0.0.4")
+        .body(metrics.render())
+}
+
+/// Live microphone capture: the client streams raw 16-bit PCM frames over the socket and
+/// gets back incremental "now playing" updates as the sliding-window match improves.
+#[get("/recognize/ws")]
+async fn recognize_ws(
+    req: HttpRequest,
+    stream: web::Payload,
+    index: web::Data<FingerprintIndex>,
+) -> actix_web::Result<HttpResponse> {
+    actix_web_actors::ws::start(ws::RecognizeSession::new(index), &req, stream)
+}
+
 #[actix_web::main]
 async fn main() -> std::io::Result<()> {
-    HttpServer::new(|| App::new().service(hello).service(health))
-        .bind("0.0.0.0:8080")?
-        .run()
-        .await
+    env_logger::init_from_env(env_logger::Env::new().default_filter_or("info"));
+
+    let index = web::Data::new(FingerprintIndex::new());
+    let metrics = web::Data::new(Metrics::new());
+
+    let mut server = HttpServer::new(move || {
+        App::new()
+            .app_data(index.clone())
+            .app_data(metrics.clone())
+            .wrap(Logger::default())
+            .wrap(timing::Timing)
+            .service(hello)
+            .service(health)
+            .service(ingest_track)
+            .service(recognize_track)
+            .service(recognize_ws)
+            .service(metrics_endpoint)
+    });
+
+    // A supervisor (or `systemfd`/cargo-watch during development) can hand us an
+    // already-open socket so a rebuild doesn't drop in-flight recognition requests.
+    server = if let Some(listener) = listenfd::ListenFd::from_env().take_tcp_listener(0)? {
+        server.listen(listener)?
+    } else {
+        server.bind("0.0.0.0:8080")?
+    };
+
+    server.run().await
 }
@@ -0,0 +1,97 @@
+//! Operational signals for the fingerprint service: request latency/counts plus the
+//! recognition hit rate, vote strength and extraction time that actually tell us whether
+//! matching is working. Registered as `web::Data` alongside the index and rendered at
+//! `/metrics` in a scrape-friendly text format.
+
+use std::fmt::Write as _;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Process-wide counters. All fields are cumulative; a scraper computes rates itself.
+#[derive(Default)]
+pub struct Metrics {
+    requests_total: AtomicU64,
+    request_duration_ms_total: AtomicU64,
+    recognize_hits_total: AtomicU64,
+    recognize_misses_total: AtomicU64,
+    recognize_votes_total: AtomicU64,
+    fingerprint_duration_ms_total: AtomicU64,
+    fingerprint_runs_total: AtomicU64,
+}
+
+impl Metrics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record_request(&self, duration_ms: u64) {
+        self.requests_total.fetch_add(1, Ordering::Relaxed);
+        self.request_duration_ms_total.fetch_add(duration_ms, Ordering::Relaxed);
+    }
+
+    /// Record a recognition attempt's outcome: `Some(votes)` on a hit, `None` on a miss.
+    pub fn record_recognition(&self, votes: Option<u32>) {
+        match votes {
+            Some(v) => {
+                self.recognize_hits_total.fetch_add(1, Ordering::Relaxed);
+                self.recognize_votes_total.fetch_add(v as u64, Ordering::Relaxed);
+            }
+            None => {
+                self.recognize_misses_total.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+    }
+
+    pub fn record_fingerprint(&self, duration_ms: u64) {
+        self.fingerprint_runs_total.fetch_add(1, Ordering::Relaxed);
+        self.fingerprint_duration_ms_total
+            .fetch_add(duration_ms, Ordering::Relaxed);
+    }
+
+    /// Render counters in the Prometheus text exposition format.
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+        let mut gauge = |name: &str, help: &str, value: u64| {
+            let _ = writeln!(out, "# HELP {name} {help}");
+            let _ = writeln!(out, "# TYPE {name} counter");
+            let _ = writeln!(out, "{name} {value}");
+        };
+
+        gauge(
+            "shazam_requests_total",
+            "Total HTTP requests handled.",
+            self.requests_total.load(Ordering::Relaxed),
+        );
+        gauge(
+            "shazam_request_duration_ms_total",
+            "Sum of request latencies in milliseconds.",
+            self.request_duration_ms_total.load(Ordering::Relaxed),
+        );
+        gauge(
+            "shazam_recognize_hits_total",
+            "Recognition attempts that found a match at or above the vote threshold.",
+            self.recognize_hits_total.load(Ordering::Relaxed),
+        );
+        gauge(
+            "shazam_recognize_misses_total",
+            "Recognition attempts that found no match.",
+            self.recognize_misses_total.load(Ordering::Relaxed),
+        );
+        gauge(
+            "shazam_recognize_votes_total",
+            "Sum of winning-bin vote counts across recognition hits.",
+            self.recognize_votes_total.load(Ordering::Relaxed),
+        );
+        gauge(
+            "shazam_fingerprint_duration_ms_total",
+            "Sum of fingerprint extraction time in milliseconds.",
+            self.fingerprint_duration_ms_total.load(Ordering::Relaxed),
+        );
+        gauge(
+            "shazam_fingerprint_runs_total",
+            "Total fingerprint extractions performed.",
+            self.fingerprint_runs_total.load(Ordering::Relaxed),
+        );
+
+        out
+    }
+}
@@ -0,0 +1,51 @@
+//! Histogram-alignment matching: turn a query's landmark hashes into a winning track.
+
+use crate::fingerprint::Landmark;
+use crate::index::{FingerprintIndex, TrackMeta};
+use std::collections::HashMap;
+
+/// Minimum vote count in a track's tallest histogram bin to call it a match.
+const MIN_VOTES: u32 = 5;
+
+/// The winning track for a query clip, plus how confident the match is.
+#[derive(Debug, Clone)]
+pub struct Match {
+    pub track_id: u32,
+    pub meta: TrackMeta,
+    /// Vote count in the winning delta bin; higher means a sharper, more confident spike.
+    pub confidence: u32,
+    /// Time offset (STFT frames) into the stored track where the clip aligns.
+    pub offset: u32,
+}
+
+/// For each query hash, accumulate votes per `(track_id, db_time - query_time)` bin and
+/// return the track with the tallest bin, provided it clears [`MIN_VOTES`].
+///
+/// A genuine match produces one sharply spiking delta bin because every landmark in a
+/// correctly-aligned clip shares the same constant time shift against the original
+/// recording; false matches scatter their votes across many deltas.
+pub fn best_match(query: &[Landmark], index: &FingerprintIndex) -> Option<Match> {
+    // (track_id, delta) -> votes
+    let mut histograms: HashMap<(u32, i64), u32> = HashMap::new();
+
+    for landmark in query {
+        for (db_time, track_id) in index.lookup(landmark.hash) {
+            let delta = db_time as i64 - landmark.anchor_time as i64;
+            *histograms.entry((track_id, delta)).or_insert(0) += 1;
+        }
+    }
+
+    let ((track_id, delta), &votes) = histograms.iter().max_by_key(|(_, &votes)| votes)?;
+
+    if votes < MIN_VOTES {
+        return None;
+    }
+
+    let meta = index.track(*track_id)?;
+    Some(Match {
+        track_id: *track_id,
+        meta,
+        confidence: votes,
+        offset: (*delta).max(0) as u32,
+    })
+}
@@ -0,0 +1,62 @@
+//! Request latency middleware: records every request's duration into the shared
+//! [`crate::metrics::Metrics`] registry so `/metrics` reflects real traffic. Counts and
+//! durations are summed across all routes; they are not broken down per route.
+
+use std::future::{ready, Ready};
+use std::rc::Rc;
+use std::time::Instant;
+
+use actix_web::dev::{forward_ready, Service, ServiceRequest, ServiceResponse, Transform};
+use actix_web::web;
+use actix_web::Error;
+use futures_util::future::LocalBoxFuture;
+
+use crate::metrics::Metrics;
+
+pub struct Timing;
+
+impl<S, B> Transform<S, ServiceRequest> for Timing
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Transform = TimingMiddleware<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(TimingMiddleware { service: Rc::new(service) }))
+    }
+}
+
+pub struct TimingMiddleware<S> {
+    service: Rc<S>,
+}
+
+impl<S, B> Service<ServiceRequest> for TimingMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let metrics = req.app_data::<web::Data<Metrics>>().cloned();
+        let service = self.service.clone();
+        let start = Instant::now();
+
+        Box::pin(async move {
+            let res = service.call(req).await?;
+            if let Some(metrics) = metrics {
+                metrics.record_request(start.elapsed().as_millis() as u64);
+            }
+            Ok(res)
+        })
+    }
+}
@@ -0,0 +1,100 @@
+//! Streaming recognition over WebSocket: a client pushes raw PCM frames continuously
+//! and gets incremental "now playing" updates back as the match improves.
+
+use std::time::Duration;
+
+use actix::{Actor, ActorContext, AsyncContext, StreamHandler};
+use actix_web::web;
+use actix_web_actors::ws;
+
+use crate::audio::TARGET_SAMPLE_RATE;
+use crate::fingerprint;
+use crate::index::FingerprintIndex;
+use crate::recognize;
+
+/// How often we recompute the match against the sliding window.
+const RECOGNIZE_INTERVAL: Duration = Duration::from_millis(500);
+/// How much audio we keep around for matching; older samples are dropped as new ones arrive.
+const WINDOW_SECONDS: u32 = 10;
+/// The client is expected to stream PCM already at [`TARGET_SAMPLE_RATE`] — the same rate
+/// the HTTP ingestion/recognition paths resample to — so a track ingested over `/tracks`
+/// at any source rate can still be matched live over this socket.
+const MAX_BUFFER_SAMPLES: usize = (TARGET_SAMPLE_RATE * WINDOW_SECONDS) as usize;
+/// Hard cap on a single incoming frame so one message can't blow past the window cap outright.
+const MAX_FRAME_BYTES: usize = MAX_BUFFER_SAMPLES * 2;
+
+/// Per-connection actor holding the sliding window of decoded samples.
+pub struct RecognizeSession {
+    index: web::Data<FingerprintIndex>,
+    buffer: Vec<f32>,
+}
+
+impl RecognizeSession {
+    pub fn new(index: web::Data<FingerprintIndex>) -> Self {
+        Self {
+            index,
+            buffer: Vec::new(),
+        }
+    }
+
+    /// Append newly received PCM, trimming the front of the buffer to enforce the window cap.
+    fn push_samples(&mut self, bytes: &[u8]) {
+        let samples = bytes
+            .chunks_exact(2)
+            .map(|b| i16::from_le_bytes([b[0], b[1]]) as f32 / i16::MAX as f32);
+        self.buffer.extend(samples);
+
+        if self.buffer.len() > MAX_BUFFER_SAMPLES {
+            let excess = self.buffer.len() - MAX_BUFFER_SAMPLES;
+            self.buffer.drain(..excess);
+        }
+    }
+
+    fn recognize_and_reply(&self, ctx: &mut ws::WebsocketContext<Self>) {
+        let landmarks = fingerprint::fingerprint(&self.buffer);
+        let reply = match recognize::best_match(&landmarks, &self.index) {
+            Some(m) => serde_json::json!({
+                "track_id": m.track_id,
+                "title": m.meta.title,
+                "artist": m.meta.artist,
+                "confidence": m.confidence,
+                "offset": m.offset,
+            }),
+            None => serde_json::json!({ "match": serde_json::Value::Null }),
+        };
+        ctx.text(reply.to_string());
+    }
+}
+
+impl Actor for RecognizeSession {
+    type Context = ws::WebsocketContext<Self>;
+
+    fn started(&mut self, ctx: &mut Self::Context) {
+        ctx.run_interval(RECOGNIZE_INTERVAL, |session, ctx| {
+            if session.buffer.is_empty() {
+                return;
+            }
+            session.recognize_and_reply(ctx);
+        });
+    }
+}
+
+impl StreamHandler<Result<ws::Message, ws::ProtocolError>> for RecognizeSession {
+    fn handle(&mut self, msg: Result<ws::Message, ws::ProtocolError>, ctx: &mut Self::Context) {
+        match msg {
+            Ok(ws::Message::Binary(bytes)) => {
+                if bytes.len() > MAX_FRAME_BYTES {
+                    ctx.text(serde_json::json!({"error": "frame too large"}).to_string());
+                    return;
+                }
+                self.push_samples(&bytes);
+            }
+            Ok(ws::Message::Ping(msg)) => ctx.pong(&msg),
+            Ok(ws::Message::Close(reason)) => {
+                ctx.close(reason);
+                ctx.stop();
+            }
+            _ => {}
+        }
+    }
+}